@@ -0,0 +1,75 @@
+use crate::http::{HttpMethod, HttpRequestError, HttpResponse, HttpVersion};
+use std::collections::HashMap;
+use std::io::{BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+
+/// A builder for outbound HTTP requests that reuses the server's own
+/// request/response types and CRLF framing, so the crate is a symmetric
+/// HTTP toolkit rather than a server-only one.
+pub struct ClientRequest {
+    method: HttpMethod,
+    target: String,
+    headers: HashMap<String, String>,
+    body: Option<Vec<u8>>,
+}
+
+impl ClientRequest {
+    pub fn new(method: HttpMethod, target: impl Into<String>) -> Self {
+        ClientRequest {
+            method,
+            target: target.into(),
+            headers: HashMap::new(),
+            body: None,
+        }
+    }
+
+    pub fn header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn body(mut self, body: Vec<u8>) -> Self {
+        self.headers
+            .insert("Content-Length".to_string(), body.len().to_string());
+        self.body = Some(body);
+        self
+    }
+
+    fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes =
+            format!("{} {} {}\r\n", self.method, self.target, HttpVersion::Http11).into_bytes();
+
+        for (key, value) in &self.headers {
+            bytes.extend_from_slice(format!("{key}: {value}\r\n").as_bytes());
+        }
+        bytes.extend_from_slice(b"\r\n");
+
+        if let Some(body) = &self.body {
+            bytes.extend_from_slice(body);
+        }
+
+        bytes
+    }
+
+    /// Opens a connection to `addr`, sends the request, and waits for the
+    /// parsed response.
+    pub fn send(mut self, addr: impl ToSocketAddrs) -> Result<HttpResponse, HttpRequestError> {
+        self.headers.entry("Host".to_string()).or_insert_with(|| {
+            addr.to_socket_addrs()
+                .ok()
+                .and_then(|mut addrs| addrs.next())
+                .map(|addr| addr.to_string())
+                .unwrap_or_default()
+        });
+
+        let mut stream = TcpStream::connect(addr)
+            .map_err(|e| HttpRequestError::InternalServerError(format!("failed to connect: {e}")))?;
+
+        stream
+            .write_all(&self.as_bytes())
+            .map_err(|e| HttpRequestError::InternalServerError(format!("failed to send request: {e}")))?;
+
+        let mut reader = BufReader::new(&mut stream);
+        HttpResponse::read_from(&mut reader)
+    }
+}
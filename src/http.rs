@@ -1,8 +1,11 @@
-use anyhow::Context;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use std::collections::HashMap;
 use std::fmt::Write;
 use std::fs::File;
+use std::io::{BufRead, Write as _};
 use std::path::Path;
+use std::time::SystemTime;
 use thiserror::Error;
 
 /// An HTTP response is made up of three parts, each separated by a CRLF (\r\n):
@@ -33,8 +36,113 @@ impl HttpResponse {
         }
     }
 
+    fn head(&self) -> String {
+        let status_line = match &self.reason {
+            Some(reason) => format!(
+                "{} {} {}\r\n",
+                self.version, self.status_code as u16, reason
+            ),
+            None => format!("{} {}\r\n", self.version, self.status_code as u16),
+        };
+
+        let headers = match &self.headers {
+            Some(headers) => headers
+                .iter()
+                .fold(String::new(), |mut output, (key, value)| {
+                    let _ = write!(output, "{}: {}\r\n", key, value);
+                    output
+                }),
+            None => "".into(),
+        };
+
+        // end headers section
+        format!("{}{}\r\n", status_line, headers)
+    }
+
     pub fn as_bytes(&self) -> Vec<u8> {
-        self.to_string().into_bytes()
+        let mut bytes = self.head().into_bytes();
+        if let Some(body) = &self.body {
+            bytes.extend_from_slice(body);
+        }
+        bytes
+    }
+
+    /// Gzip-compresses the body when `request`'s `Accept-Encoding` lists a
+    /// supported encoding, updating `Content-Encoding` and `Content-Length`
+    /// to match; leaves the response untouched otherwise. Meant to be run
+    /// as a post-processing step over every outgoing response.
+    pub fn compress_if_supported(&mut self, request: &HttpRequest) {
+        let Some(body) = &self.body else {
+            return;
+        };
+
+        let accepts_gzip = request
+            .headers
+            .as_ref()
+            .and_then(|headers| headers.get("Accept-Encoding"))
+            .is_some_and(|value| {
+                value
+                    .split(',')
+                    .any(|encoding| encoding.trim().eq_ignore_ascii_case("gzip"))
+            });
+
+        if !accepts_gzip {
+            return;
+        }
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        if encoder.write_all(body).is_err() {
+            return;
+        }
+        let Ok(compressed) = encoder.finish() else {
+            return;
+        };
+
+        let headers = self.headers.get_or_insert_with(HashMap::new);
+        headers.insert("Content-Encoding".to_string(), "gzip".to_string());
+        headers.insert("Content-Length".to_string(), compressed.len().to_string());
+        self.body = Some(compressed);
+    }
+
+    /// Reads a response off of `reader`, using the same CRLF framing as
+    /// `HttpRequest::read_from`: the status line and headers up to the
+    /// blank line that ends them, then exactly `Content-Length` bytes of
+    /// body. Used by the `client` module to read back a server's reply.
+    pub fn read_from<R: BufRead>(reader: &mut R) -> Result<Self, HttpRequestError> {
+        let lines = read_head_lines(reader)?;
+        if lines.is_empty() {
+            return Err(HttpRequestError::BadRequest(
+                "response is malformed".to_string(),
+            ));
+        }
+
+        let mut status_line = lines[0].splitn(3, ' ');
+        let version = HttpVersion::from(status_line.next().unwrap_or(""));
+        let status_code = status_line
+            .next()
+            .and_then(|code| code.parse::<u16>().ok())
+            .and_then(StatusCode::from_u16)
+            .ok_or_else(|| HttpRequestError::BadRequest("status line is malformed".to_string()))?;
+        let reason = status_line.next().map(str::to_string);
+
+        let headers: HashMap<_, _> = lines
+            .iter()
+            .skip(1)
+            .filter_map(|s| {
+                s.split_once(": ")
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+            })
+            .collect();
+
+        let body = read_body(reader, &headers)?;
+
+        Ok(HttpResponse {
+            version,
+            status_code,
+            reason,
+            headers: Some(headers),
+            body,
+        })
     }
 
     pub fn ok(headers: Headers, body: Body) -> Self {
@@ -69,59 +177,104 @@ impl HttpResponse {
     }
 
     pub fn get_file(request: &HttpRequest, directory: &str) -> Result<Self, HttpRequestError> {
-        // assuming file is not in a subdirectory
-        let file_loc = request.target.split('/').last();
-
-        let file_loc = match file_loc {
-            Some(loc) => loc,
+        let file_loc = match request.params.get("filepath") {
+            Some(loc) => loc.as_str(),
             None => {
                 return Err(HttpRequestError::BadRequest(
                     "could not extract file location".to_string(),
                 ))
             }
         };
+        let file_loc = sanitize_file_path(file_loc)?;
 
         // assuming dir ends in '/'
-        let file = std::fs::read_to_string(format!("{}{}", directory, file_loc));
-
-        let file = match file {
-            Ok(f) => f,
-            Err(_) => {
-                return Err(HttpRequestError::NotFound(format!(
-                    "failed to read file {} in dir {}",
-                    file_loc, directory
-                )))
-            }
+        let path = format!("{}{}", directory, file_loc);
+
+        let not_found = || {
+            HttpRequestError::NotFound(format!(
+                "failed to read file {} in dir {}",
+                file_loc, directory
+            ))
         };
 
-        let headers = HashMap::from([
+        let metadata = std::fs::metadata(&path).map_err(|_| not_found())?;
+        let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        let etag = weak_etag(&metadata, modified);
+        let last_modified = httpdate::fmt_http_date(modified);
+
+        if is_not_modified(request, &etag, modified) {
+            let headers = HashMap::from([
+                ("ETag".to_string(), etag),
+                ("Last-Modified".to_string(), last_modified),
+            ]);
+            return Ok(HttpResponse::new(
+                StatusCode::NotModified,
+                Some("Not Modified".to_string()),
+                Some(headers),
+                None,
+            ));
+        }
+
+        let file = std::fs::read(&path).map_err(|_| not_found())?;
+        let total = file.len();
+
+        let range = request
+            .headers
+            .as_ref()
+            .and_then(|headers| headers.get("Range"))
+            .and_then(|range| parse_range(range, total));
+
+        if let Some(RangeRequest::Unsatisfiable) = range {
+            let headers = HashMap::from([("Content-Range".to_string(), format!("bytes */{total}"))]);
+            return Ok(HttpResponse::new(
+                StatusCode::RangeNotSatisfiable,
+                Some("Range Not Satisfiable".to_string()),
+                Some(headers),
+                None,
+            ));
+        }
+
+        let (status, reason, body, content_range) = match range {
+            Some(RangeRequest::Satisfiable(start, end)) => (
+                StatusCode::PartialContent,
+                "Partial Content",
+                file[start..=end].to_vec(),
+                Some(format!("bytes {start}-{end}/{total}")),
+            ),
+            _ => (StatusCode::Ok, "OK", file, None),
+        };
+
+        let mut headers = HashMap::from([
             (
                 "Content-Type".to_string(),
-                "application/octet-stream".to_string(),
+                content_type_for(file_loc).to_string(),
             ),
-            ("Content-Length".to_string(), file.len().to_string()),
+            ("Content-Length".to_string(), body.len().to_string()),
+            ("ETag".to_string(), etag),
+            ("Last-Modified".to_string(), last_modified),
         ]);
+        if let Some(content_range) = content_range {
+            headers.insert("Content-Range".to_string(), content_range);
+        }
 
         Ok(HttpResponse::new(
-            StatusCode::Ok,
-            Some("OK".to_string()),
+            status,
+            Some(reason.to_string()),
             Some(headers),
-            Some(file),
+            Some(body),
         ))
     }
 
     pub fn post_file(request: &HttpRequest, directory: &str) -> Result<Self, HttpRequestError> {
-        // assuming file is not in a subdirectory
-        let file_name = request.target.split('/').last();
-
-        let file_name = match file_name {
-            Some(loc) => loc,
+        let file_name = match request.params.get("filepath") {
+            Some(loc) => loc.as_str(),
             None => {
                 return Err(HttpRequestError::BadRequest(
                     "could not extract file name".to_string(),
                 ))
             }
         };
+        let file_name = sanitize_file_path(file_name)?;
 
         // assuming dir ends in '/'
         std::fs::create_dir_all(directory).expect("could not create directory");
@@ -141,7 +294,7 @@ impl HttpResponse {
         // fill file with body content
         {
             use std::io::Write;
-            file.write_all(request.body.as_ref().unwrap().as_bytes())
+            file.write_all(request.body.as_deref().unwrap_or_default())
                 .expect("couldnt write body to file");
         }
 
@@ -153,13 +306,15 @@ impl HttpResponse {
         ))
     }
 
-    pub fn echo(request: &HttpRequest) -> anyhow::Result<HttpResponse> {
-        let res_body = request
-            .target
-            .split('/')
-            .last()
-            .context("could not get last element of /echo/ endpoint")
-            .unwrap();
+    pub fn echo(request: &HttpRequest) -> Result<HttpResponse, HttpRequestError> {
+        let res_body = match request.params.get("msg") {
+            Some(msg) => msg.as_str(),
+            None => {
+                return Err(HttpRequestError::BadRequest(
+                    "could not extract /echo/ message".to_string(),
+                ))
+            }
+        };
 
         let headers = HashMap::from([
             ("Content-Type".to_string(), "text/plain".to_string()),
@@ -170,7 +325,7 @@ impl HttpResponse {
             StatusCode::Ok,
             Some("OK".to_string()),
             Some(headers),
-            Some(res_body.to_string()),
+            Some(res_body.as_bytes().to_vec()),
         ))
     }
 
@@ -196,40 +351,137 @@ impl HttpResponse {
             StatusCode::Ok,
             Some("OK".to_string()),
             Some(headers),
-            Some(agent_header.to_string()),
+            Some(agent_header.as_bytes().to_vec()),
         ))
     }
 }
 
-impl std::fmt::Display for HttpResponse {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        let status_line = match &self.reason {
-            Some(reason) => format!(
-                "{} {} {}\r\n",
-                self.version, self.status_code as u16, reason
-            ),
-            None => format!("{} {}\r\n", self.version, self.status_code as u16),
-        };
+/// A weak validator derived from a file's size and modification time, good
+/// enough to detect whether a client's cached copy is still fresh.
+fn weak_etag(metadata: &std::fs::Metadata, modified: SystemTime) -> String {
+    let mtime_secs = modified
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    format!("W/\"{:x}-{:x}\"", mtime_secs, metadata.len())
+}
 
-        let headers = match &self.headers {
-            Some(headers) => headers
-                .iter()
-                .fold(String::new(), |mut output, (key, value)| {
-                    let _ = write!(output, "{}: {}\r\n", key, value);
-                    output
-                }),
-            None => "".into(),
-        };
+/// `If-None-Match` takes precedence over `If-Modified-Since` when both are
+/// present, per the conditional-GET rules in RFC 7232.
+fn is_not_modified(request: &HttpRequest, etag: &str, modified: SystemTime) -> bool {
+    let Some(headers) = &request.headers else {
+        return false;
+    };
+
+    if let Some(if_none_match) = headers.get("If-None-Match") {
+        return if_none_match
+            .split(',')
+            .map(str::trim)
+            .any(|candidate| candidate == "*" || candidate == etag);
+    }
 
-        // end headers section
-        let headers = format!("{}\r\n", headers);
+    if let Some(if_modified_since) = headers.get("If-Modified-Since") {
+        if let Ok(since) = httpdate::parse_http_date(if_modified_since) {
+            return modified <= since;
+        }
+    }
 
-        let body = match &self.body {
-            Some(body) => body.to_string(),
-            None => "".into(),
-        };
+    false
+}
+
+enum RangeRequest {
+    Satisfiable(usize, usize),
+    Unsatisfiable,
+}
+
+/// Parses a `Range: bytes=start-end` header (open-ended `start-` and
+/// suffix `-length` forms are also accepted) against a body of `total`
+/// bytes. Returns `None` for anything that isn't a byte range, which
+/// callers should treat as "serve the whole file".
+fn parse_range(range_header: &str, total: usize) -> Option<RangeRequest> {
+    let spec = range_header.strip_prefix("bytes=")?;
+    let (start, end) = spec.split_once('-')?;
+
+    let bounds = match (start.trim(), end.trim()) {
+        ("", suffix) => {
+            let len = suffix.parse::<usize>().ok()?;
+            Some((total.saturating_sub(len), total.saturating_sub(1)))
+        }
+        (start, "") => start.parse::<usize>().ok().map(|s| (s, total.saturating_sub(1))),
+        (start, end) => match (start.parse::<usize>(), end.parse::<usize>()) {
+            (Ok(start), Ok(end)) => Some((start, end)),
+            _ => None,
+        },
+    }?;
+
+    Some(match bounds {
+        (start, end) if total > 0 && start <= end && end < total => {
+            RangeRequest::Satisfiable(start, end)
+        }
+        _ => RangeRequest::Unsatisfiable,
+    })
+}
+
+/// Rejects a `*filepath`-captured route param that would escape `directory`
+/// once joined onto it - a `..` component (or an absolute path, which the
+/// router never produces but is rejected defensively anyway) would otherwise
+/// let `/files/../secret.txt` read or write outside `directory`.
+fn sanitize_file_path(file_loc: &str) -> Result<&str, HttpRequestError> {
+    use std::path::Component;
+
+    let is_safe = !file_loc.is_empty()
+        && Path::new(file_loc)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+
+    if is_safe {
+        Ok(file_loc)
+    } else {
+        Err(HttpRequestError::BadRequest(format!(
+            "invalid file path: {file_loc}"
+        )))
+    }
+}
 
-        write!(f, "{}{}{}", status_line, headers, body)
+/// Looks up the `Content-Type` for a served file based on its extension,
+/// defaulting to `application/octet-stream` for anything unrecognized.
+pub fn content_type_for(path: &str) -> &'static str {
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+
+    match extension.as_str() {
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "js" => "text/javascript",
+        "json" => "application/json",
+        "txt" => "text/plain",
+        "xml" => "application/xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "ico" => "image/x-icon",
+        "pdf" => "application/pdf",
+        "wasm" => "application/wasm",
+        _ => "application/octet-stream",
+    }
+}
+
+impl std::fmt::Display for HttpResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.head())?;
+
+        // the body may contain arbitrary bytes (e.g. an uploaded binary file),
+        // so this is a lossy, display-only rendering - `as_bytes` is what actually
+        // goes out on the wire.
+        if let Some(body) = &self.body {
+            write!(f, "{}", String::from_utf8_lossy(body))?;
+        }
+
+        Ok(())
     }
 }
 
@@ -240,19 +492,24 @@ pub struct HttpRequest {
     pub version: HttpVersion,
     pub headers: Headers,
     pub body: Body,
+    /// Captured `:name` (and path-tail) segments from the route that
+    /// matched this request, populated by the `Router`.
+    pub params: HashMap<String, String>,
 }
 
-impl TryFrom<Vec<String>> for HttpRequest {
-    type Error = HttpRequestError;
-
-    fn try_from(data: Vec<String>) -> Result<Self, HttpRequestError> {
-        println!("{data:?}");
-        if data.is_empty() {
+impl HttpRequest {
+    /// Parses the request line and headers out of the lines making up the
+    /// head of a request (everything before the blank line that ends it).
+    fn parse_head(
+        lines: &[String],
+    ) -> Result<(HttpMethod, String, HttpVersion, HashMap<String, String>), HttpRequestError> {
+        if lines.is_empty() {
             return Err(HttpRequestError::BadRequest(
                 "request is malformed".to_string(),
             ));
         }
-        let mut request_line = data[0].split(' ');
+
+        let mut request_line = lines[0].split(' ');
         if request_line.clone().count() != 3 {
             return Err(HttpRequestError::BadRequest(
                 "request line is malformed".to_string(),
@@ -263,30 +520,106 @@ impl TryFrom<Vec<String>> for HttpRequest {
         let target = String::from(request_line.next().unwrap());
         let version = HttpVersion::from(request_line.next().unwrap());
 
-        let headers: HashMap<_, _> = data
+        let headers: HashMap<_, _> = lines
             .iter()
             .skip(1)
-            .take_while(|data| !data.is_empty())
             .filter_map(|s| {
                 s.split_once(": ")
                     .map(|(k, v)| (k.to_string(), v.to_string()))
             })
             .collect();
 
-        let body = data.last().map(|data| data.to_string());
-        println!("{body:?}");
+        Ok((method, target, version, headers))
+    }
+
+    /// Reads a request off of `reader`, framing it correctly so binary
+    /// bodies survive intact: the head (request line + headers) is read a
+    /// line at a time up to the blank line that ends it, then exactly
+    /// `Content-Length` bytes are read as the body.
+    ///
+    /// Returns `Ok(None)` when the peer closes the connection, or the read
+    /// times out, before sending anything - there's no request to respond
+    /// to, but it isn't an error either (this is the normal way a
+    /// keep-alive connection ends).
+    pub fn read_from<R: BufRead>(reader: &mut R) -> Result<Option<Self>, HttpRequestError> {
+        let lines = read_head_lines(reader)?;
+        if lines.is_empty() {
+            return Ok(None);
+        }
+
+        let (method, target, version, headers) = Self::parse_head(&lines)?;
+        let body = read_body(reader, &headers)?;
 
-        Ok(HttpRequest {
+        Ok(Some(HttpRequest {
             method,
             target,
             version,
             headers: Some(headers),
             body,
+            params: HashMap::new(),
+        }))
+    }
+}
+
+/// Reads lines off of `reader` up to (and not including) the blank line
+/// that ends an HTTP head - the framing a request line + headers and a
+/// status line + headers share. An I/O error (including a read timeout)
+/// before any line has been read is treated the same as a clean EOF, since
+/// that's the normal way an idle keep-alive connection ends.
+fn read_head_lines<R: BufRead>(reader: &mut R) -> Result<Vec<String>, HttpRequestError> {
+    let mut lines = Vec::new();
+    loop {
+        let mut line = String::new();
+        let bytes_read = match reader.read_line(&mut line) {
+            Ok(n) => n,
+            Err(_) if lines.is_empty() => return Ok(lines),
+            Err(e) => {
+                return Err(HttpRequestError::BadRequest(format!(
+                    "failed to read head: {e}"
+                )))
+            }
+        };
+        if bytes_read == 0 {
+            break;
+        }
+
+        let line = line.trim_end_matches(['\r', '\n']).to_string();
+        if line.is_empty() {
+            break;
+        }
+        lines.push(line);
+    }
+
+    Ok(lines)
+}
+
+/// Reads exactly `Content-Length` bytes of body off of `reader`, per
+/// `headers`.
+fn read_body<R: BufRead>(
+    reader: &mut R,
+    headers: &HashMap<String, String>,
+) -> Result<Body, HttpRequestError> {
+    let content_length = headers
+        .get("Content-Length")
+        .map(|len| {
+            len.parse::<usize>()
+                .map_err(|_| HttpRequestError::BadRequest(format!("invalid Content-Length: {len}")))
         })
+        .transpose()?
+        .unwrap_or(0);
+
+    if content_length == 0 {
+        return Ok(None);
     }
+
+    let mut buf = vec![0u8; content_length];
+    reader
+        .read_exact(&mut buf)
+        .map_err(|e| HttpRequestError::BadRequest(format!("failed to read body: {e}")))?;
+    Ok(Some(buf))
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HttpMethod {
     Get,
     Post,
@@ -317,9 +650,27 @@ impl From<&str> for HttpMethod {
     }
 }
 
+impl std::fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let method = match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Patch => "PATCH",
+            HttpMethod::Delete => "DELETE",
+            HttpMethod::Connect => "CONNECT",
+            HttpMethod::Head => "HEAD",
+            HttpMethod::Options => "OPTIONS",
+            HttpMethod::Trace => "TRACE",
+            HttpMethod::NotImp => "",
+        };
+        write!(f, "{}", method)
+    }
+}
+
 pub type Reason = Option<String>;
 pub type Headers = Option<HashMap<String, String>>;
-pub type Body = Option<String>;
+pub type Body = Option<Vec<u8>>;
 
 #[derive(Debug)]
 pub enum HttpVersion {
@@ -356,8 +707,11 @@ impl std::fmt::Display for HttpVersion {
 pub enum StatusCode {
     Ok = 200,
     Created = 201,
+    PartialContent = 206,
+    NotModified = 304,
     BadRequest = 400,
     NotFound = 404,
+    RangeNotSatisfiable = 416,
     InternalServerError = 500,
 }
 
@@ -365,8 +719,12 @@ impl StatusCode {
     pub fn from_u16(code: u16) -> Option<Self> {
         match code {
             200 => Some(StatusCode::Ok),
+            201 => Some(StatusCode::Created),
+            206 => Some(StatusCode::PartialContent),
+            304 => Some(StatusCode::NotModified),
             400 => Some(StatusCode::BadRequest),
             404 => Some(StatusCode::NotFound),
+            416 => Some(StatusCode::RangeNotSatisfiable),
             500 => Some(StatusCode::InternalServerError),
             _ => None,
         }
@@ -382,3 +740,105 @@ pub enum HttpRequestError {
     #[error("not found")]
     NotFound(String),
 }
+
+impl From<HttpRequestError> for HttpResponse {
+    fn from(err: HttpRequestError) -> Self {
+        println!("error: {:?}", err);
+        match err {
+            HttpRequestError::BadRequest(_) => HttpResponse::bad_request(None, None),
+            HttpRequestError::InternalServerError(_) => HttpResponse::internal_server_error(),
+            HttpRequestError::NotFound(_) => HttpResponse::not_found(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_range_rejects_non_byte_units() {
+        assert!(parse_range("items=0-10", 100).is_none());
+    }
+
+    #[test]
+    fn parse_range_start_end() {
+        assert!(matches!(
+            parse_range("bytes=0-9", 100),
+            Some(RangeRequest::Satisfiable(0, 9))
+        ));
+    }
+
+    #[test]
+    fn parse_range_open_ended_start() {
+        assert!(matches!(
+            parse_range("bytes=90-", 100),
+            Some(RangeRequest::Satisfiable(90, 99))
+        ));
+    }
+
+    #[test]
+    fn parse_range_suffix_length() {
+        assert!(matches!(
+            parse_range("bytes=-10", 100),
+            Some(RangeRequest::Satisfiable(90, 99))
+        ));
+    }
+
+    #[test]
+    fn parse_range_beyond_total_is_unsatisfiable() {
+        assert!(matches!(
+            parse_range("bytes=200-300", 100),
+            Some(RangeRequest::Unsatisfiable)
+        ));
+    }
+
+    #[test]
+    fn parse_range_empty_file_is_unsatisfiable() {
+        assert!(matches!(
+            parse_range("bytes=0-0", 0),
+            Some(RangeRequest::Unsatisfiable)
+        ));
+    }
+
+    #[test]
+    fn sanitize_file_path_rejects_parent_dir_traversal() {
+        assert!(sanitize_file_path("../secret.txt").is_err());
+        assert!(sanitize_file_path("a/../../secret.txt").is_err());
+    }
+
+    #[test]
+    fn sanitize_file_path_rejects_absolute_paths() {
+        assert!(sanitize_file_path("/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn sanitize_file_path_accepts_plain_relative_paths() {
+        assert_eq!(sanitize_file_path("a/b.txt").unwrap(), "a/b.txt");
+    }
+
+    #[test]
+    fn content_type_for_known_and_unknown_extensions() {
+        assert_eq!(content_type_for("index.html"), "text/html");
+        assert_eq!(content_type_for("archive.tar.gz"), "application/octet-stream");
+        assert_eq!(content_type_for("no_extension"), "application/octet-stream");
+    }
+
+    #[test]
+    fn is_not_modified_honors_if_none_match() {
+        let request = HttpRequest {
+            method: HttpMethod::Get,
+            target: "/files/a.txt".to_string(),
+            version: HttpVersion::Http11,
+            headers: Some(HashMap::from([(
+                "If-None-Match".to_string(),
+                "W/\"abc-1\"".to_string(),
+            )])),
+            body: None,
+            params: HashMap::new(),
+        };
+
+        assert!(is_not_modified(&request, "W/\"abc-1\"", SystemTime::UNIX_EPOCH));
+        assert!(!is_not_modified(&request, "W/\"different\"", SystemTime::UNIX_EPOCH));
+    }
+}
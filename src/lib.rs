@@ -0,0 +1,4 @@
+pub mod client;
+pub mod http;
+pub mod router;
+pub mod thread_pool;
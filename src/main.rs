@@ -1,69 +1,103 @@
 use http_server_starter_rust::http::*;
+use http_server_starter_rust::router::Router;
+use http_server_starter_rust::thread_pool::ThreadPool;
 use std::{
+    collections::HashMap,
     env,
     io::{prelude::*, BufReader},
     net::{TcpListener, TcpStream},
+    sync::Arc,
+    thread,
+    time::Duration,
 };
 
-fn handle_files(request: &HttpRequest, working_dir: &str) -> HttpResponse {
-    let response = match request.method {
-        HttpMethod::Get => HttpResponse::get_file(request, working_dir),
-        HttpMethod::Post => HttpResponse::post_file(request, working_dir),
-        _ => Ok(HttpResponse::not_found()),
-    };
-
-    match response {
-        Ok(res) => res,
-        Err(err) => match err {
-            HttpRequestError::BadRequest(e) => {
-                println!("error: {:?}", e);
-                HttpResponse::bad_request(None, None)
-            }
-            HttpRequestError::InternalServerError(e) => {
-                println!("error: {:?}", e);
-                HttpResponse::internal_server_error()
-            }
-            HttpRequestError::NotFound(e) => {
-                println!("error: {:?}", e);
-                HttpResponse::not_found()
-            }
-        },
+const DEFAULT_IDLE_TIMEOUT_SECS: u64 = 30;
+
+fn build_router(working_dir: Arc<str>) -> Router {
+    let mut router = Router::new();
+
+    router
+        .route(HttpMethod::Get, "/", |_req| Ok(HttpResponse::ok(None, None)))
+        .route(HttpMethod::Get, "/user-agent", HttpResponse::user_agent)
+        .route(HttpMethod::Get, "/echo/:msg", HttpResponse::echo);
+
+    let get_dir = Arc::clone(&working_dir);
+    router.route(HttpMethod::Get, "/files/*filepath", move |req| {
+        HttpResponse::get_file(req, &get_dir)
+    });
+
+    router.route(HttpMethod::Post, "/files/*filepath", move |req| {
+        HttpResponse::post_file(req, &working_dir)
+    });
+
+    router
+}
+
+// HTTP/1.1 defaults to keep-alive, HTTP/1.0 defaults to close - either can be
+// overridden by an explicit `Connection` header.
+fn should_keep_alive(req: &HttpRequest) -> bool {
+    let connection_header = req
+        .headers
+        .as_ref()
+        .and_then(|headers| headers.get("Connection"))
+        .map(|value| value.to_ascii_lowercase());
+
+    match connection_header.as_deref() {
+        Some("close") => false,
+        Some("keep-alive") => true,
+        _ => matches!(req.version, HttpVersion::Http11),
     }
 }
 
-fn handle_connection(mut stream: TcpStream, working_dir: &str) {
-    let buf_reader = BufReader::new(&mut stream);
-    let raw_request: Vec<String> = buf_reader.lines().map(|result| result.unwrap()).collect();
-
-    let request = HttpRequest::try_from(raw_request);
-
-    let response = match request {
-        Ok(req) => match req.target.as_str() {
-            "/" => HttpResponse::ok(None, None),
-            "/user-agent" => match HttpResponse::user_agent(&req) {
-                Ok(res) => res,
-                Err(e) => {
-                    println!("error: {:?}", e);
-                    HttpResponse::bad_request(None, None)
-                }
-            },
-            path if path.starts_with("/echo/") => match HttpResponse::echo(&req) {
-                Ok(res) => res,
-                Err(e) => {
-                    println!("error: {:?}", e);
-                    HttpResponse::bad_request(None, None)
-                }
-            },
-            path if path.starts_with("/files/") => handle_files(&req, working_dir),
-            _ => HttpResponse::not_found(),
-        },
-        Err(e) => {
-            println!("error: {:?}", e);
-            HttpResponse::bad_request(None, None)
+fn handle_connection(mut stream: TcpStream, router: &Router, idle_timeout: Duration) {
+    if stream.set_read_timeout(Some(idle_timeout)).is_err() {
+        return;
+    }
+
+    // Built once per connection and reused across keep-alive iterations - a
+    // fresh `BufReader` per request would silently drop any bytes it had
+    // already buffered past the end of the request it was asked to read.
+    let mut buf_reader = BufReader::new(&mut stream);
+
+    loop {
+        let request = HttpRequest::read_from(&mut buf_reader);
+
+        let mut req = match request {
+            Ok(Some(req)) => req,
+            Ok(None) => break,
+            Err(e) => {
+                println!("error: {:?}", e);
+                let _ = buf_reader
+                    .get_mut()
+                    .write_all(&HttpResponse::bad_request(None, None).as_bytes());
+                break;
+            }
+        };
+
+        let keep_alive = should_keep_alive(&req);
+        let mut response = router.dispatch(&mut req);
+        response.compress_if_supported(&req);
+
+        if !keep_alive {
+            response
+                .headers
+                .get_or_insert_with(HashMap::new)
+                .insert("Connection".to_string(), "close".to_string());
+        }
+
+        if buf_reader.get_mut().write_all(&response.as_bytes()).is_err() || !keep_alive {
+            break;
         }
-    };
+    }
+}
 
-    stream.write_all(&response.as_bytes()).unwrap();
+// returns the value following `flag` in `args`, e.g. `parse_flag(args, "--directory")`
+// for `["prog", "--directory", "/tmp/"]` returns `Some("/tmp/")`
+fn parse_flag<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
 }
 
 // TODO:
@@ -76,15 +110,26 @@ fn handle_connection(mut stream: TcpStream, working_dir: &str) {
 fn main() {
     let args: Vec<String> = env::args().collect();
     println!("executing with args: {args:?}");
-    let working_dir = if args.len() > 1 { args[2].as_str() } else { "" };
+    let working_dir: Arc<str> = Arc::from(parse_flag(&args, "--directory").unwrap_or(""));
+    let pool_size = parse_flag(&args, "--pool-size")
+        .and_then(|n| n.parse().ok())
+        .unwrap_or_else(|| thread::available_parallelism().map(|n| n.get()).unwrap_or(4));
+    let idle_timeout = Duration::from_secs(
+        parse_flag(&args, "--idle-timeout")
+            .and_then(|secs| secs.parse().ok())
+            .unwrap_or(DEFAULT_IDLE_TIMEOUT_SECS),
+    );
 
     let listener = TcpListener::bind("127.0.0.1:4221").unwrap();
+    let pool = ThreadPool::new(pool_size);
+    let router = Arc::new(build_router(working_dir));
 
     for stream in listener.incoming() {
         match stream {
             Ok(stream) => {
                 println!("accepted new connection");
-                handle_connection(stream, working_dir);
+                let router = Arc::clone(&router);
+                pool.execute(move || handle_connection(stream, &router, idle_timeout));
             }
             Err(e) => {
                 println!("error: {}", e);
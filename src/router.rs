@@ -0,0 +1,175 @@
+use crate::http::{HttpMethod, HttpRequest, HttpRequestError, HttpResponse};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub type Handler = Arc<dyn Fn(&HttpRequest) -> Result<HttpResponse, HttpRequestError> + Send + Sync>;
+
+enum Segment {
+    /// A fixed path component, e.g. the `echo` in `/echo/:msg`.
+    Literal(String),
+    /// A `:name` component that captures a single path segment.
+    Param(String),
+    /// A `*name` component that captures everything from here to the end
+    /// of the path (joined back together with `/`), for routes like the
+    /// file server that need to accept nested paths.
+    Tail(String),
+}
+
+impl Segment {
+    fn parse(raw: &str) -> Self {
+        if let Some(name) = raw.strip_prefix('*') {
+            Segment::Tail(name.to_string())
+        } else if let Some(name) = raw.strip_prefix(':') {
+            Segment::Param(name.to_string())
+        } else {
+            Segment::Literal(raw.to_string())
+        }
+    }
+}
+
+struct Route {
+    method: HttpMethod,
+    segments: Vec<Segment>,
+    handler: Handler,
+}
+
+impl Route {
+    fn matches(&self, path: &str) -> Option<HashMap<String, String>> {
+        let parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let mut params = HashMap::new();
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                Segment::Tail(name) => {
+                    let rest = parts.get(i..)?;
+                    if rest.is_empty() {
+                        return None;
+                    }
+                    params.insert(name.clone(), rest.join("/"));
+                    return Some(params);
+                }
+                Segment::Literal(literal) => {
+                    if parts.get(i) != Some(&literal.as_str()) {
+                        return None;
+                    }
+                }
+                Segment::Param(name) => {
+                    params.insert(name.clone(), (*parts.get(i)?).to_string());
+                }
+            }
+        }
+
+        (parts.len() == self.segments.len()).then_some(params)
+    }
+}
+
+/// Matches requests against registered `(method, pattern)` routes and
+/// dispatches to the matching handler, replacing the hand-rolled
+/// `match`/`starts_with` dispatch that used to live in `main`.
+///
+/// Patterns are `/`-separated and support three kinds of segment: literal
+/// segments (`echo`), `:name` segments that capture a single path
+/// component, and a trailing `*name` segment that captures the rest of the
+/// path (for endpoints like the file server that need nested paths).
+#[derive(Default)]
+pub struct Router {
+    routes: Vec<Route>,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Router { routes: Vec::new() }
+    }
+
+    /// Registers `handler` for requests whose method matches `method` and
+    /// whose target matches `pattern`.
+    pub fn route<F>(&mut self, method: HttpMethod, pattern: &str, handler: F) -> &mut Self
+    where
+        F: Fn(&HttpRequest) -> Result<HttpResponse, HttpRequestError> + Send + Sync + 'static,
+    {
+        let segments = pattern.split('/').filter(|s| !s.is_empty()).map(Segment::parse).collect();
+
+        self.routes.push(Route {
+            method,
+            segments,
+            handler: Arc::new(handler),
+        });
+        self
+    }
+
+    /// Finds the first route matching `request`, stores its captured
+    /// params on `request`, and invokes its handler. Falls back to `404`
+    /// when nothing matches.
+    pub fn dispatch(&self, request: &mut HttpRequest) -> HttpResponse {
+        let target = request.target.split('?').next().unwrap_or(&request.target).to_string();
+
+        for route in &self.routes {
+            if route.method != request.method {
+                continue;
+            }
+
+            if let Some(params) = route.matches(&target) {
+                request.params = params;
+                return match (route.handler)(request) {
+                    Ok(response) => response,
+                    Err(err) => HttpResponse::from(err),
+                };
+            }
+        }
+
+        HttpResponse::not_found()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::HttpMethod;
+
+    fn route(pattern: &str) -> Route {
+        Route {
+            method: HttpMethod::Get,
+            segments: pattern
+                .split('/')
+                .filter(|s| !s.is_empty())
+                .map(Segment::parse)
+                .collect(),
+            handler: Arc::new(|_req| Ok(HttpResponse::ok(None, None))),
+        }
+    }
+
+    #[test]
+    fn literal_segments_must_match_exactly() {
+        let route = route("/echo/hello");
+        assert!(route.matches("/echo/hello").is_some());
+        assert!(route.matches("/echo/world").is_none());
+        assert!(route.matches("/echo/hello/extra").is_none());
+    }
+
+    #[test]
+    fn param_segment_captures_single_component() {
+        let route = route("/echo/:msg");
+        let params = route.matches("/echo/hello").unwrap();
+        assert_eq!(params.get("msg"), Some(&"hello".to_string()));
+        assert!(route.matches("/echo/hello/world").is_none());
+    }
+
+    #[test]
+    fn tail_segment_captures_remaining_components_joined_by_slash() {
+        let route = route("/files/*filepath");
+        let params = route.matches("/files/a/b/c.txt").unwrap();
+        assert_eq!(params.get("filepath"), Some(&"a/b/c.txt".to_string()));
+        assert!(route.matches("/files").is_none());
+    }
+
+    #[test]
+    fn tail_segment_captures_dotdot_components_verbatim() {
+        // `Route::matches` itself does not sanitize `..` - it's a plain
+        // path-tail capture. It's up to callers (`HttpResponse::get_file`
+        // and `post_file`) to reject traversal before touching the
+        // filesystem; this test pins down what the router hands them.
+        let route = route("/files/*filepath");
+        let params = route.matches("/files/../secret.txt").unwrap();
+        assert_eq!(params.get("filepath"), Some(&"../secret.txt".to_string()));
+    }
+}